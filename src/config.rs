@@ -0,0 +1,97 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use termion::color;
+
+/// User-tunable "typewriter paper" look, loaded from `~/.config/cutt/config`
+/// (or `$XDG_CONFIG_HOME/cutt/config`) with sane defaults when missing.
+pub struct Config {
+    pub scheme:      String,
+    pub status_bg:   color::Rgb,
+    pub paper_bg:    color::Rgb,
+    pub paper_width: usize,
+    pub tab_stop:    usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::for_scheme("default")
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        config_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    /// Builds the named preset's colors. Unknown names fall back to `"default"`.
+    fn for_scheme(name: &str) -> Self {
+        let (status_bg, paper_bg) = match name {
+            "light"     => (color::Rgb(200, 200, 200), color::Rgb(255, 255, 240)),
+            "solarized" => (color::Rgb(  7,  54,  66), color::Rgb(  0,  43,  54)),
+            _           => (color::Rgb(  0,   0,   0), color::Rgb( 20,  20,  20)),
+        };
+        Self {
+            scheme: name.to_string(),
+            status_bg,
+            paper_bg,
+            paper_width: 80,
+            tab_stop: 4,
+        }
+    }
+
+    fn parse(contents: &str) -> Self {
+        let mut scheme = String::from("default");
+        let mut overrides = Vec::new();
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim();
+            if key == "scheme" {
+                scheme = value.to_string();
+            } else {
+                overrides.push((key, value));
+            }
+        }
+
+        // The named scheme supplies the base palette; individual keys
+        // below override whatever the scheme picked.
+        let mut config = Self::for_scheme(&scheme);
+        for (key, value) in overrides {
+            match key {
+                "status_bg"   => if let Some(rgb) = parse_rgb(value) { config.status_bg = rgb; },
+                "paper_bg"    => if let Some(rgb) = parse_rgb(value) { config.paper_bg = rgb; },
+                "paper_width" => if let Ok(n) = value.parse() { config.paper_width = n; },
+                "tab_stop"    => if let Ok(n) = value.parse() { config.tab_stop = n; },
+                _ => (),
+            }
+        }
+        config
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    let config_home = match env::var("XDG_CONFIG_HOME") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => PathBuf::from(env::var("HOME").ok()?).join(".config"),
+    };
+    Some(config_home.join("cutt").join("config"))
+}
+
+fn parse_rgb(value: &str) -> Option<color::Rgb> {
+    let mut parts = value.split(',').map(str::trim);
+    let r: u8 = parts.next()?.parse().ok()?;
+    let g: u8 = parts.next()?.parse().ok()?;
+    let b: u8 = parts.next()?.parse().ok()?;
+    Some(color::Rgb(r, g, b))
+}
@@ -0,0 +1,152 @@
+use crate::Position;
+use crate::Row;
+use crate::SearchDirection;
+use crate::DEFAULT_TAB_STOP;
+use std::fs;
+use std::io::Write;
+
+pub struct Document {
+    rows:      Vec<Row>,
+    file_name: Option<String>,
+    dirty:     bool,
+    tab_stop:  usize,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self::new(DEFAULT_TAB_STOP)
+    }
+}
+
+impl Document {
+    pub fn new(tab_stop: usize) -> Self {
+        Self {
+            rows: Vec::new(),
+            file_name: None,
+            dirty: false,
+            tab_stop,
+        }
+    }
+
+    pub fn open(filename: &str, tab_stop: usize) -> Result<Self, std::io::Error> {
+        let contents = fs::read_to_string(filename)?;
+        let rows = contents
+            .lines()
+            .map(|line| Row::with_tab_stop(line, tab_stop))
+            .collect();
+        Ok(Self {
+            rows,
+            file_name: Some(filename.to_string()),
+            dirty: false,
+            tab_stop,
+        })
+    }
+
+    pub fn row(&self, index: usize) -> Option<&Row> {
+        self.rows.get(index)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn insert(&mut self, at: &Position, c: char) {
+        if at.y > self.rows.len() {
+            return;
+        }
+        self.dirty = true;
+        if c == '\n' {
+            self.insert_newline(at);
+            return;
+        }
+        if at.y == self.rows.len() {
+            self.rows.push(Row::with_tab_stop(c.to_string().as_str(), self.tab_stop));
+        } else if let Some(row) = self.rows.get_mut(at.y) {
+            row.insert(at.x, c);
+        }
+    }
+
+    fn insert_newline(&mut self, at: &Position) {
+        if at.y == self.rows.len() {
+            self.rows.push(Row::with_tab_stop("", self.tab_stop));
+            return;
+        }
+        let new_row = self.rows[at.y].split(at.x);
+        self.rows.insert(at.y + 1, new_row);
+    }
+
+    pub fn delete(&mut self, at: &Position) {
+        let len = self.rows.len();
+        if at.y >= len {
+            return;
+        }
+        self.dirty = true;
+        if at.x == self.rows[at.y].len() && at.y + 1 < len {
+            let next_row = self.rows.remove(at.y + 1);
+            self.rows[at.y].append(&next_row);
+        } else {
+            self.rows[at.y].delete(at.x);
+        }
+    }
+
+    /// Scans rows starting from `at` for `query`, wrapping direction handling
+    /// to the caller, and returns the row/column of the first hit.
+    pub fn find(&self, query: &str, at: &Position, direction: SearchDirection) -> Option<Position> {
+        if at.y >= self.rows.len() {
+            return None;
+        }
+
+        let mut position = Position { x: at.x, y: at.y };
+        let start = if direction == SearchDirection::Forward { at.y } else { 0 };
+        let end   = if direction == SearchDirection::Forward { self.rows.len() } else { at.y.saturating_add(1) };
+
+        for _ in start..end {
+            if let Some(row) = self.rows.get(position.y) {
+                if let Some(x) = row.find(query, position.x, direction) {
+                    position.x = x;
+                    return Some(position);
+                }
+                match direction {
+                    SearchDirection::Forward => {
+                        position.y = position.y.saturating_add(1);
+                        position.x = 0;
+                    }
+                    SearchDirection::Backward => {
+                        if position.y == 0 {
+                            break;
+                        }
+                        position.y -= 1;
+                        position.x = self.rows[position.y].len();
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+        None
+    }
+
+    pub fn save(&mut self) -> Result<(), std::io::Error> {
+        if let Some(file_name) = &self.file_name {
+            let mut file = fs::File::create(file_name)?;
+            for row in &self.rows {
+                file.write_all(row.as_str().as_bytes())?;
+                file.write_all(b"\n")?;
+            }
+            self.dirty = false;
+        }
+        Ok(())
+    }
+}
@@ -1,18 +1,26 @@
+use crate::Config;
 use crate::Document;
 use crate::Row;
+use crate::SearchDirection;
 use crate::Terminal;
 use std::env;
+use std::thread;
+use std::time::Duration;
 use termion::color;
 use termion::event::Key;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(30);
 
-const STATUS_BG_COLOR: color::Rgb = color::Rgb(  0,   0,   0);
-const PAPER_BG_COLOR:  color::Rgb = color::Rgb( 20,  20,  20);
-const PAPER_WIDTH: usize = 80;
 const NUM_WIDTH: usize = 5;
+const QUIT_TIMES: u8 = 3;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct Position {
+    /// In `cursor_position`, a grapheme index into the row (not a byte or
+    /// char offset); in `offset` and on-screen positions, a display column.
     pub x: usize,
     pub y: usize,
 }
@@ -22,53 +30,117 @@ pub struct Editor {
     cursor_position: Position,
     offset:          Position,
     document:        Document,
+    status_message:  String,
+    quit_times:      u8,
+    last_size:       (u16, u16),
+    last_frame:      Vec<String>,
+    config:          Config,
 }
 
 impl Editor {
     pub fn run(&mut self) {
         loop {
+            if self.check_resize() {
+                self.clamp_cursor();
+            }
+
             if let Err(error) = self.refresh_screen() {
                 die(&error);
             }
-            
+
             if self.should_quit { break; }
 
-            if let Err(error) = self.process_keypress() {
-                die(&error);
+            match self.terminal.poll_key() {
+                Ok(Some(key)) => {
+                    if let Err(error) = self.process_keypress(key) {
+                        die(&error);
+                    }
+                }
+                Ok(None) => thread::sleep(POLL_INTERVAL),
+                Err(error) => die(&error),
             }
         }
     }
 
+    fn check_resize(&mut self) -> bool {
+        let size = self.terminal.refresh_size();
+        if size != self.last_size {
+            self.last_size = size;
+            self.last_frame.clear();
+            Terminal::clear_screen();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn clamp_cursor(&mut self) {
+        let height = self.document.len().saturating_sub(1);
+        if self.cursor_position.y > height {
+            self.cursor_position.y = height;
+        }
+        let width = self.document.row(self.cursor_position.y).map_or(0, Row::len);
+        if self.cursor_position.x > width {
+            self.cursor_position.x = width;
+        }
+        self.scroll();
+    }
+
     pub fn default() -> Self {
+        let config = Config::load();
+
         let args: Vec<String> = env::args().collect();
         let document = if args.len() > 1 {
             let file_name = &args[1];
-            Document::open(&file_name).unwrap_or_default()
+            Document::open(file_name, config.tab_stop)
+                .unwrap_or_else(|_| Document::new(config.tab_stop))
         } else {
-            Document::default()
+            Document::new(config.tab_stop)
         };
 
+        let terminal = Terminal::new().expect("Terminal init failed.");
+        let last_size = (terminal.size().width, terminal.size().height);
+
         Self {
             should_quit: false,
-            terminal: Terminal::default().expect("Terminal init failed."),
+            terminal,
             document,
             cursor_position: Position::default(),
             offset: Position::default(),
+            status_message: String::from("HELP: Ctrl-C = quit | Ctrl-S = save"),
+            quit_times: QUIT_TIMES,
+            last_size,
+            last_frame: Vec::new(),
+            config,
         }
     }
 
-    fn refresh_screen(&self) -> Result<(), std::io::Error> {
+    /// Builds the intended screen as one string per terminal row, diffs it
+    /// against the previously rendered frame, and only repaints the rows
+    /// that actually changed.
+    fn refresh_screen(&mut self) -> Result<(), std::io::Error> {
         Terminal::cursor_hide();
-        Terminal::cursor_position(&Position::default());
 
         if self.should_quit {
             Terminal::clear_screen();
-            print!("cutted exiting.\r\n")
+            Terminal::cursor_position(&Position::default());
+            print!("cutted exiting.\r\n");
         } else {
-            self.draw_rows();
-            self.draw_status_bar();
+            let mut frame = self.render_rows();
+            frame.push(self.render_status_bar());
+            frame.push(self.render_message_bar());
+
+            for (index, line) in frame.iter().enumerate() {
+                if self.last_frame.get(index) != Some(line) {
+                    Terminal::cursor_position(&Position { x: 0, y: index });
+                    Terminal::clear_until_newline();
+                    print!("{}", line);
+                }
+            }
+            self.last_frame = frame;
+
             Terminal::cursor_position(&Position {
-                x: self.cursor_position.x.saturating_sub(self.offset.x),
+                x: self.cursor_rx().saturating_sub(self.offset.x),
                 y: self.cursor_position.y.saturating_sub(self.offset.y),
             });
         }
@@ -77,10 +149,30 @@ impl Editor {
         Terminal::flush()
     }
 
-    fn process_keypress(&mut self) -> Result<(), std::io::Error> {
-        let pressed_key = Terminal::read_key()?;
+    fn process_keypress(&mut self, pressed_key: Key) -> Result<(), std::io::Error> {
         match pressed_key {
-            Key::Ctrl('c') => self.should_quit = true,
+            Key::Ctrl('c') => {
+                if self.quit_times > 0 && self.document.is_dirty() {
+                    self.status_message = format!(
+                        "WARNING! File has unsaved changes. Press Ctrl-C {} more time(s) to quit.",
+                        self.quit_times
+                    );
+                    self.quit_times -= 1;
+                    return Ok(());
+                }
+                self.should_quit = true;
+            }
+            Key::Ctrl('s') => self.save(),
+            Key::Ctrl('f') => self.search(),
+            Key::Char(c) => {
+                self.document.insert(&self.cursor_position, c);
+                self.move_cursor(Key::Right);
+            }
+            Key::Backspace if self.cursor_position.x > 0 || self.cursor_position.y > 0 => {
+                self.move_cursor(Key::Left);
+                self.document.delete(&self.cursor_position);
+            }
+            Key::Delete => self.document.delete(&self.cursor_position),
             Key::Up       |
             Key::Down     |
             Key::Left     |
@@ -92,11 +184,105 @@ impl Editor {
             _ => (),
         }
         self.scroll();
+        self.quit_times = QUIT_TIMES;
         Ok(())
     }
 
+    fn save(&mut self) {
+        if self.document.save().is_ok() {
+            self.status_message = String::from("File saved successfully.");
+        } else {
+            self.status_message = String::from("Error writing file!");
+        }
+    }
+
+    /// Reads a line of input interactively in the message area, invoking
+    /// `callback` after every keypress except the Enter or Esc that ends it.
+    /// Returns `None` if the user cancels with Esc or submits an empty line.
+    fn prompt<C>(&mut self, prompt: &str, mut callback: C) -> Result<Option<String>, std::io::Error>
+    where
+        C: FnMut(&mut Self, Key, &String),
+    {
+        let mut result = String::new();
+        loop {
+            if self.check_resize() {
+                self.clamp_cursor();
+            }
+
+            self.status_message = format!("{}{}", prompt, result);
+            self.refresh_screen()?;
+            let key = loop {
+                if let Some(key) = self.terminal.poll_key()? {
+                    break key;
+                }
+                thread::sleep(POLL_INTERVAL);
+            };
+            match key {
+                Key::Backspace => {
+                    result.pop();
+                }
+                Key::Char('\n') => break,
+                Key::Char(c) if !c.is_control() => result.push(c),
+                Key::Esc => {
+                    result.clear();
+                    break;
+                }
+                _ => (),
+            }
+            callback(self, key, &result);
+        }
+        self.status_message = String::new();
+        if result.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(result))
+    }
+
+    fn search(&mut self) {
+        let old_position = self.cursor_position;
+        let old_offset   = self.offset;
+        let mut direction = SearchDirection::Forward;
+
+        let query = self
+            .prompt(
+                "Search (Esc to cancel, Arrows to navigate): ",
+                |editor, key, query| {
+                    let mut stepped = false;
+                    match key {
+                        Key::Right | Key::Down => {
+                            direction = SearchDirection::Forward;
+                            editor.move_cursor(Key::Right);
+                            stepped = true;
+                        }
+                        Key::Left | Key::Up => direction = SearchDirection::Backward,
+                        _ => direction = SearchDirection::Forward,
+                    }
+                    if let Some(position) = editor.document.find(query, &editor.cursor_position, direction) {
+                        editor.cursor_position = position;
+                        editor.scroll();
+                    } else if stepped {
+                        editor.move_cursor(Key::Left);
+                    }
+                },
+            )
+            .unwrap_or(None);
+
+        if query.is_none() {
+            self.cursor_position = old_position;
+            self.offset = old_offset;
+        }
+        self.scroll();
+    }
+
+    fn cursor_rx(&self) -> usize {
+        self.document
+            .row(self.cursor_position.y)
+            .map_or(0, |row| row.render_x(self.cursor_position.x))
+    }
+
     fn scroll(&mut self) {
-        let Position { x, y } = self.cursor_position;
+        let y = self.cursor_position.y;
+        let x = self.cursor_rx();
         let width  = self.terminal.size().width  as usize;
         // Subtract 1 for status line:
         let height = self.terminal.size().height as usize;
@@ -180,7 +366,7 @@ impl Editor {
         self.cursor_position = Position { x, y }
     }
 
-    fn draw_welcome_message(&self) {
+    fn welcome_message(&self) -> String {
         let mut welcome_message = format!("cutted v.{}", VERSION);
         let width = self.terminal.size().width as usize;
         let len = welcome_message.len();
@@ -188,52 +374,81 @@ impl Editor {
         let spacer = " ".repeat(padding.saturating_sub(1));
         welcome_message = format!("~{}{}", spacer, welcome_message);
         welcome_message.truncate(width);
-        print!("{}\r\n", welcome_message);
+        welcome_message
     }
 
-    pub fn draw_row(&self, row: &Row) {
-        let width = self.terminal.size().width as usize;
-        let start = self.offset.x;
-        let end   = self.offset.x + width;
-        let len   = row.len();
-
-        if start < PAPER_WIDTH {
-            Terminal::set_bg_color(PAPER_BG_COLOR);
-            print!("{}", row.render(start, PAPER_WIDTH));
-            if start > len {
-                print!("{}", " ".repeat(PAPER_WIDTH.saturating_sub(start)));
-            } else {
-                print!("{}", " ".repeat(PAPER_WIDTH.saturating_sub(len)));
-            }
-            Terminal::reset_bg_color();
-            print!("{}\r\n", row.render(PAPER_WIDTH, end));
+    pub fn render_row(&self, row: &Row) -> String {
+        let width       = self.terminal.size().width as usize;
+        let paper_width = self.config.paper_width;
+        let start       = self.offset.x;
+        let end         = self.offset.x + width;
+        let mut line    = String::new();
+
+        if start < paper_width {
+            let rendered_width = row.render_width(start, paper_width);
+            line.push_str(&format!("{}", color::Bg(self.config.paper_bg)));
+            line.push_str(&row.render(start, paper_width));
+            line.push_str(&" ".repeat(paper_width.saturating_sub(start).saturating_sub(rendered_width)));
+            line.push_str(&format!("{}", color::Bg(color::Reset)));
+            line.push_str(&row.render(paper_width, end));
         } else {
-            print!("{}\r\n", row.render(start, end));
+            line.push_str(&row.render(start, end));
         }
+        line
     }
 
-    fn draw_rows(&self) {
+    fn render_rows(&self) -> Vec<String> {
         let height = self.terminal.size().height;
-        for terminal_row in 0..height {
-            Terminal::clear_current_line();
+        let mut lines = Vec::with_capacity(height as usize);
 
-            if let Some(row) = self.document.row(terminal_row as usize
+        for terminal_row in 0..height {
+            let line = if let Some(row) = self.document.row(terminal_row as usize
                                                + self.offset.y) {
-                self.draw_row(row);
+                self.render_row(row)
             } else if self.document.is_empty() && terminal_row == height / 2 {
-                 self.draw_welcome_message();
+                self.welcome_message()
             } else {
-                print!("~\r\n");
-            }
+                String::from("~")
+            };
+            lines.push(line);
         }
+        lines
     }
 
-    fn draw_status_bar(&self) {
-        let spacer = "|".repeat(self.terminal.size().width as usize);
-        Terminal::set_bg_color(STATUS_BG_COLOR);
-        print!("{}", spacer);
-        Terminal::reset_bg_color();
+    fn render_status_bar(&self) -> String {
+        let width = self.terminal.size().width as usize;
+
+        let mut file_info = truncate_graphemes(
+            self.document.file_name().unwrap_or("[No Name]"),
+            width.saturating_sub(10).max(1),
+        );
+        if self.document.is_dirty() {
+            file_info.push_str(" [modified]");
+        }
+
+        let line_indicator = format!(
+            "{}/{} , {}",
+            self.cursor_position.y.saturating_add(1),
+            self.document.len(),
+            self.cursor_position.x.saturating_add(1),
+        );
+
+        let len = UnicodeWidthStr::width(file_info.as_str()) + UnicodeWidthStr::width(line_indicator.as_str());
+        let padding = " ".repeat(width.saturating_sub(len));
+        let status = truncate_graphemes(&format!("{}{}{}", file_info, padding, line_indicator), width);
+
+        format!("{}{}{}", color::Bg(self.config.status_bg), status, color::Bg(color::Reset))
     }
+
+    fn render_message_bar(&self) -> String {
+        truncate_graphemes(&self.status_message, self.terminal.size().width as usize)
+    }
+}
+
+/// Truncates `s` to at most `max_graphemes` grapheme clusters, unlike
+/// `String::truncate` this never panics on a multi-byte character.
+fn truncate_graphemes(s: &str, max_graphemes: usize) -> String {
+    s.graphemes(true).take(max_graphemes).collect()
 }
 
 fn die(e: &std::io::Error) {
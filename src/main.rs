@@ -0,0 +1,18 @@
+mod config;
+mod document;
+mod editor;
+mod row;
+mod terminal;
+
+pub use config::Config;
+pub use document::Document;
+pub use editor::Position;
+pub use row::Row;
+pub use row::SearchDirection;
+pub use row::DEFAULT_TAB_STOP;
+pub use terminal::Terminal;
+use editor::Editor;
+
+fn main() {
+    Editor::default().run();
+}
@@ -0,0 +1,228 @@
+use std::cmp;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Fallback tab stop used when a row is built without an explicit one
+/// (e.g. via `Row::default()` or the `From<&str>` impl).
+pub const DEFAULT_TAB_STOP: usize = 4;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum SearchDirection {
+    Forward,
+    Backward,
+}
+
+pub struct Row {
+    string:           String,
+    graphemes:        Vec<String>,
+    render:           String,
+    render_graphemes: Vec<String>,
+    tab_stop:         usize,
+}
+
+impl Default for Row {
+    fn default() -> Self {
+        Self {
+            string: String::new(),
+            graphemes: Vec::new(),
+            render: String::new(),
+            render_graphemes: Vec::new(),
+            tab_stop: DEFAULT_TAB_STOP,
+        }
+    }
+}
+
+impl From<&str> for Row {
+    fn from(slice: &str) -> Self {
+        Self::with_tab_stop(slice, DEFAULT_TAB_STOP)
+    }
+}
+
+impl Row {
+    pub fn with_tab_stop(slice: &str, tab_stop: usize) -> Self {
+        let mut row = Self {
+            string: String::from(slice),
+            tab_stop,
+            ..Self::default()
+        };
+        row.update();
+        row
+    }
+
+    fn update(&mut self) {
+        self.graphemes = self.string.graphemes(true).map(String::from).collect();
+
+        let mut render = String::new();
+        let mut col = 0;
+        for grapheme in &self.graphemes {
+            if grapheme == "\t" {
+                let spaces = self.tab_stop - (col % self.tab_stop);
+                render.push_str(&" ".repeat(spaces));
+                col += spaces;
+            } else {
+                render.push_str(grapheme);
+                col += UnicodeWidthStr::width(grapheme.as_str());
+            }
+        }
+        self.render = render;
+        self.render_graphemes = self.render.graphemes(true).map(String::from).collect();
+    }
+
+    /// Renders the display columns in `[start, end)` as a single string,
+    /// with tabs already expanded to this row's tab stop.
+    pub fn render(&self, start: usize, end: usize) -> String {
+        let end_index   = self.column_to_index(end);
+        let start_index = cmp::min(self.column_to_index(start), end_index);
+        self.render_graphemes[start_index..end_index].concat()
+    }
+
+    /// Converts a display column into the index of the first rendered
+    /// grapheme that starts at or after that column, so `render` can
+    /// slice by column without splitting a wide glyph in half.
+    fn column_to_index(&self, column: usize) -> usize {
+        let mut width = 0;
+        for (index, grapheme) in self.render_graphemes.iter().enumerate() {
+            if width >= column {
+                return index;
+            }
+            width += UnicodeWidthStr::width(grapheme.as_str());
+        }
+        self.render_graphemes.len()
+    }
+
+    /// Display width (in terminal columns) of the rendered text in `[start, end)`.
+    /// Combining marks count as zero columns, wide glyphs count as two.
+    pub fn render_width(&self, start: usize, end: usize) -> usize {
+        UnicodeWidthStr::width(self.render(start, end).as_str())
+    }
+
+    /// Converts a logical character index (`x`) into a render column (`rx`),
+    /// expanding any tabs before it to the next tab-stop boundary.
+    pub fn render_x(&self, x: usize) -> usize {
+        let mut rx = 0;
+        for grapheme in self.graphemes.iter().take(x) {
+            if grapheme == "\t" {
+                rx += self.tab_stop - (rx % self.tab_stop);
+            } else {
+                rx += UnicodeWidthStr::width(grapheme.as_str());
+            }
+        }
+        rx
+    }
+
+    /// Number of logical characters (graphemes) in the row, pre-tab-expansion.
+    pub fn len(&self) -> usize {
+        self.graphemes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.graphemes.is_empty()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    pub fn insert(&mut self, at: usize, c: char) {
+        if at >= self.graphemes.len() {
+            self.string.push(c);
+        } else {
+            let mut result = String::new();
+            for (index, grapheme) in self.graphemes.iter().enumerate() {
+                if index == at {
+                    result.push(c);
+                }
+                result.push_str(grapheme);
+            }
+            self.string = result;
+        }
+        self.update();
+    }
+
+    pub fn delete(&mut self, at: usize) {
+        if at >= self.graphemes.len() {
+            return;
+        }
+        let mut result = String::new();
+        for (index, grapheme) in self.graphemes.iter().enumerate() {
+            if index != at {
+                result.push_str(grapheme);
+            }
+        }
+        self.string = result;
+        self.update();
+    }
+
+    /// Moves `other`'s content onto the end of this row.
+    pub fn append(&mut self, other: &Self) {
+        self.string.push_str(&other.string);
+        self.update();
+    }
+
+    /// Splits off everything at and after grapheme index `at` into a new row.
+    pub fn split(&mut self, at: usize) -> Self {
+        let mut remainder = String::new();
+        let mut kept = String::new();
+        for (index, grapheme) in self.graphemes.iter().enumerate() {
+            if index < at {
+                kept.push_str(grapheme);
+            } else {
+                remainder.push_str(grapheme);
+            }
+        }
+        self.string = kept;
+        self.update();
+        Self::with_tab_stop(remainder.as_str(), self.tab_stop)
+    }
+
+    /// Searches this row's rendered text for `query`, starting at logical
+    /// grapheme index `at`, and returns the logical grapheme index of the
+    /// match (if any); a match inside a tab's expansion maps to the tab.
+    pub fn find(&self, query: &str, at: usize, direction: SearchDirection) -> Option<usize> {
+        if at > self.graphemes.len() || query.is_empty() {
+            return None;
+        }
+
+        // Index into `render_graphemes` where each logical grapheme starts
+        // (plus the end), since a tab renders as several space graphemes.
+        let mut starts = vec![0];
+        let mut rx = 0;
+        for grapheme in &self.graphemes {
+            let span = if grapheme == "\t" { self.tab_stop - (rx % self.tab_stop) } else { 1 };
+            rx += if grapheme == "\t" { span } else { UnicodeWidthStr::width(grapheme.as_str()) };
+            starts.push(starts[starts.len() - 1] + span);
+        }
+        let at = cmp::min(starts[at], self.render_graphemes.len());
+
+        let (start, end) = match direction {
+            SearchDirection::Forward  => (at, self.render_graphemes.len()),
+            SearchDirection::Backward => (0, at),
+        };
+        let substring: String = self.render_graphemes[start..end].concat();
+
+        let matching_byte_index = match direction {
+            SearchDirection::Forward  => substring.find(query),
+            SearchDirection::Backward => substring.rfind(query),
+        }?;
+
+        substring
+            .grapheme_indices(true)
+            .enumerate()
+            .find(|(_, (byte_index, _))| *byte_index == matching_byte_index)
+            .map(|(grapheme_index, _)| start + grapheme_index)
+            .and_then(|hit| starts.iter().rposition(|&s| s <= hit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_slices_by_display_column_not_grapheme_count() {
+        let row = Row::with_tab_stop("你world", DEFAULT_TAB_STOP);
+        // "你" occupies 2 display columns, so column 2 lands right after it,
+        // not after 2 graphemes (which would cut into "world").
+        assert_eq!(row.render(2, 100), "world");
+    }
+}
@@ -0,0 +1,102 @@
+use crate::Position;
+use std::io::{self, stdout, Write};
+use termion::color;
+use termion::event::Key;
+use termion::input::{Keys, TermRead};
+use termion::raw::{IntoRawMode, RawTerminal};
+use termion::AsyncReader;
+
+pub struct Size {
+    pub width:  u16,
+    pub height: u16,
+}
+
+pub struct Terminal {
+    size:    Size,
+    _stdout: RawTerminal<std::io::Stdout>,
+    keys:    Keys<AsyncReader>,
+}
+
+impl Terminal {
+    pub fn new() -> Result<Self, std::io::Error> {
+        let size = termion::terminal_size()?;
+        Ok(Self {
+            size: Size {
+                width:  size.0,
+                height: size.1.saturating_sub(2),
+            },
+            _stdout: stdout().into_raw_mode()?,
+            keys: termion::async_stdin().keys(),
+        })
+    }
+
+    pub fn size(&self) -> &Size {
+        &self.size
+    }
+
+    /// Re-reads the terminal's current dimensions and returns the updated
+    /// `size()` (height without the two status rows) as a `(width, height)`
+    /// pair, for change detection across frames.
+    pub fn refresh_size(&mut self) -> (u16, u16) {
+        if let Ok(size) = termion::terminal_size() {
+            self.size = Size {
+                width:  size.0,
+                height: size.1.saturating_sub(2),
+            };
+        }
+        (self.size.width, self.size.height)
+    }
+
+    pub fn clear_screen() {
+        print!("{}", termion::clear::All);
+    }
+
+    pub fn clear_current_line() {
+        print!("{}", termion::clear::CurrentLine);
+    }
+
+    pub fn clear_until_newline() {
+        print!("{}", termion::clear::UntilNewline);
+    }
+
+    pub fn cursor_position(position: &Position) {
+        let Position { x, y } = position;
+        let x = x.saturating_add(1) as u16;
+        let y = y.saturating_add(1) as u16;
+        print!("{}", termion::cursor::Goto(x, y));
+    }
+
+    pub fn cursor_hide() {
+        print!("{}", termion::cursor::Hide);
+    }
+
+    pub fn cursor_show() {
+        print!("{}", termion::cursor::Show);
+    }
+
+    pub fn flush() -> Result<(), std::io::Error> {
+        io::stdout().flush()
+    }
+
+    /// Non-blocking: returns the next pressed key if one is already
+    /// buffered, or `Ok(None)` if nothing has arrived yet.
+    pub fn poll_key(&mut self) -> Result<Option<Key>, std::io::Error> {
+        self.keys.next().transpose()
+    }
+
+    pub fn set_bg_color(color: color::Rgb) {
+        print!("{}", color::Bg(color));
+    }
+
+    pub fn reset_bg_color() {
+        print!("{}", color::Bg(color::Reset));
+    }
+
+    pub fn set_fg_color(color: color::Rgb) {
+        print!("{}", color::Fg(color));
+    }
+
+    pub fn reset_fg_color() {
+        print!("{}", color::Fg(color::Reset));
+    }
+}